@@ -1,29 +1,140 @@
 use serde::de::{self, DeserializeSeed, SeqAccess, Visitor};
-use serde::Deserialize;
+use serde::ser::{
+    self, SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple,
+    SerializeTupleStruct, SerializeTupleVariant,
+};
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display};
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 struct Point {
     x: i32,
     y: i32,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 struct Point64 {
     x: i64,
     y: i64,
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
 struct Compound {
     points: (Point, Point),
     more_points: Vec<Point64>,
 }
 
+#[derive(Deserialize, Debug, PartialEq)]
+enum Shape {
+    Empty,
+    Square(i32),
+    Rectangle(i32, i32),
+    Named { x: i32, y: i32 },
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct WithOptional {
+    x: i32,
+    label: Option<i32>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Flag {
+    id: i32,
+    enabled: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Widths {
+    a: i8,
+    b: u8,
+    c: i128,
+    d: u128,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Extras {
+    a: u8,
+    b: bool,
+    c: String,
+    d: Option<i32>,
+}
+
+// Exercises the positional/map disambiguation in `deserialize_struct`: the
+// first field is itself a `String`, which previously collided with the
+// map-encoding heuristic.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct LeadingStr {
+    name: String,
+    n: i32,
+}
+
 #[derive(Debug, Clone)]
 pub enum Value {
+    I8(i8),
+    I16(i16),
     I32(i32),
     I64(i64),
+    I128(i128),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Str(String),
+    Null,
+}
+
+// The common, bounds-checked conversion every `next_*` integer getter goes
+// through when the deserializer is in lax mode: widen whatever integer
+// `Value` is actually stored into an `i128`, then narrow that into the
+// requested width, erroring with `Error::OutOfRange` if it doesn't fit.
+fn coerce_int<T>(value: &Value, target: &'static str) -> Result<T, Error>
+where
+    T: TryFrom<i128>,
+{
+    let wide = match *value {
+        Value::I8(v) => v as i128,
+        Value::I16(v) => v as i128,
+        Value::I32(v) => v as i128,
+        Value::I64(v) => v as i128,
+        Value::I128(v) => v,
+        Value::U8(v) => v as i128,
+        Value::U16(v) => v as i128,
+        Value::U32(v) => v as i128,
+        Value::U64(v) => v as i128,
+        Value::U128(v) => i128::try_from(v).map_err(|_| Error::OutOfRange {
+            value: i128::MAX,
+            target,
+        })?,
+        Value::Str(_) | Value::Null => {
+            return Err(Error::TypeMismatch {
+                expected: target,
+                found: value_type_name(value),
+            })
+        }
+    };
+    T::try_from(wide).map_err(|_| Error::OutOfRange {
+        value: wide,
+        target,
+    })
+}
+
+fn value_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::I8(_) => "i8",
+        Value::I16(_) => "i16",
+        Value::I32(_) => "i32",
+        Value::I64(_) => "i64",
+        Value::I128(_) => "i128",
+        Value::U8(_) => "u8",
+        Value::U16(_) => "u16",
+        Value::U32(_) => "u32",
+        Value::U64(_) => "u64",
+        Value::U128(_) => "u128",
+        Value::Str(_) => "str",
+        Value::Null => "null",
+    }
 }
 
 #[test]
@@ -62,11 +173,318 @@ fn harder_test() {
     );
 }
 
+#[test]
+fn round_trips_through_serializer() {
+    let point = Point { x: 1, y: 2 };
+    let values = to_values(&point).unwrap();
+    let result: Point = from_values(&values).unwrap();
+    assert_eq!(result, point);
+
+    let compound = Compound {
+        points: (Point { x: 1, y: 2 }, Point { x: 3, y: 4 }),
+        more_points: vec![Point64 { x: 5, y: 6 }],
+    };
+    let values = to_values(&compound).unwrap();
+    let result: Compound = from_values(&values).unwrap();
+    assert_eq!(result, compound);
+
+    // Covers the leaf widths/bool/str/Option paths, not just i32/i64/tuple/seq.
+    let extras = Extras {
+        a: 200,
+        b: true,
+        c: "hello".to_string(),
+        d: Some(5),
+    };
+    let values = to_values(&extras).unwrap();
+    let result: Extras = from_values(&values).unwrap();
+    assert_eq!(result, extras);
+
+    let extras = Extras {
+        a: 0,
+        b: false,
+        c: String::new(),
+        d: None,
+    };
+    let values = to_values(&extras).unwrap();
+    let result: Extras = from_values(&values).unwrap();
+    assert_eq!(result, extras);
+
+    // A struct whose first field is a `String` must still round-trip through
+    // the positional encoding, not be misread as map-encoded.
+    let leading_str = LeadingStr {
+        name: "bob".to_string(),
+        n: 7,
+    };
+    let values = to_values(&leading_str).unwrap();
+    let result: LeadingStr = from_values(&values).unwrap();
+    assert_eq!(result, leading_str);
+
+    // Covers the i128/u128 leaf paths specifically.
+    let widths = Widths {
+        a: -1,
+        b: 2,
+        c: i128::MIN,
+        d: u128::MAX,
+    };
+    let values = to_values(&widths).unwrap();
+    let result: Widths = from_values(&values).unwrap();
+    assert_eq!(result, widths);
+}
+
+#[test]
+fn struct_from_map_encoding() {
+    let vector: Vec<Value> = vec![
+        Value::Str("x".to_string()),
+        Value::I32(1),
+        Value::Str("y".to_string()),
+        Value::I32(2),
+    ];
+    let result: Point = from_values(&vector).unwrap();
+    assert_eq!(result, Point { x: 1, y: 2 });
+}
+
+#[test]
+fn struct_from_map_encoding_ignores_unknown_keys() {
+    // The leading key must name a real field so `deserialize_struct` picks
+    // the map path; the unknown key in the middle is what this test covers.
+    let vector: Vec<Value> = vec![
+        Value::Str("x".to_string()),
+        Value::I32(1),
+        Value::Str("z".to_string()),
+        Value::I32(99),
+        Value::Str("y".to_string()),
+        Value::I32(2),
+    ];
+    let result: Point = from_values(&vector).unwrap();
+    assert_eq!(result, Point { x: 1, y: 2 });
+}
+
+#[test]
+fn enum_variants() {
+    let vector: Vec<Value> = vec![Value::I32(0)];
+    let result: Shape = from_values(&vector).unwrap();
+    assert_eq!(result, Shape::Empty);
+
+    let vector: Vec<Value> = vec![Value::I32(1), Value::I32(5)];
+    let result: Shape = from_values(&vector).unwrap();
+    assert_eq!(result, Shape::Square(5));
+
+    let vector: Vec<Value> = vec![Value::I32(2), Value::I32(3), Value::I32(4)];
+    let result: Shape = from_values(&vector).unwrap();
+    assert_eq!(result, Shape::Rectangle(3, 4));
+
+    let vector: Vec<Value> = vec![Value::I32(3), Value::I32(1), Value::I32(2)];
+    let result: Shape = from_values(&vector).unwrap();
+    assert_eq!(result, Shape::Named { x: 1, y: 2 });
+
+    let vector: Vec<Value> = vec![Value::I32(99)];
+    let result: Result<Shape, _> = from_values(&vector);
+    assert!(result.is_err());
+}
+
+#[test]
+fn optional_fields() {
+    let vector: Vec<Value> = vec![Value::I32(1), Value::Null];
+    let result: WithOptional = from_values(&vector).unwrap();
+    assert_eq!(result, WithOptional { x: 1, label: None });
+
+    let vector: Vec<Value> = vec![Value::I32(1), Value::I32(2)];
+    let result: WithOptional = from_values(&vector).unwrap();
+    assert_eq!(
+        result,
+        WithOptional {
+            x: 1,
+            label: Some(2)
+        }
+    );
+
+    // A trailing `Option<T>` field may simply be absent from the stream.
+    let vector: Vec<Value> = vec![Value::I32(1)];
+    let result: WithOptional = from_values(&vector).unwrap();
+    assert_eq!(result, WithOptional { x: 1, label: None });
+
+    // But a missing non-optional field is still a hard error.
+    let vector: Vec<Value> = Vec::new();
+    let result: Result<WithOptional, _> = from_values(&vector);
+    assert!(result.is_err());
+}
+
+#[test]
+fn lax_numeric_coercion() {
+    // Strict mode still rejects a width mismatch.
+    let vector: Vec<Value> = vec![Value::I32(1), Value::I32(2)];
+    let result: Result<Point64, _> = from_values(&vector);
+    assert!(result.is_err());
+
+    // Lax mode promotes i32 -> i64 losslessly.
+    let result: Point64 = from_values_lax(&vector).unwrap();
+    assert_eq!(result, Point64 { x: 1, y: 2 });
+
+    // Lax mode narrows i64 -> i32 when it fits.
+    let vector: Vec<Value> = vec![Value::I64(1), Value::I64(2)];
+    let result: Point = from_values_lax(&vector).unwrap();
+    assert_eq!(result, Point { x: 1, y: 2 });
+
+    // ...but not when it doesn't.
+    let vector: Vec<Value> = vec![Value::I64(i64::MAX), Value::I64(2)];
+    let result: Result<Point, _> = from_values_lax(&vector);
+    assert_eq!(
+        result,
+        Err(Error::OutOfRange {
+            value: i64::MAX as i128,
+            target: "i32",
+        })
+    );
+}
+
+#[test]
+fn bool_from_numeric_and_string_tokens() {
+    let result: Flag = from_values(&[Value::I32(1), Value::I32(1)]).unwrap();
+    assert_eq!(
+        result,
+        Flag {
+            id: 1,
+            enabled: true
+        }
+    );
+
+    let result: Flag = from_values(&[Value::I32(1), Value::I32(0)]).unwrap();
+    assert_eq!(
+        result,
+        Flag {
+            id: 1,
+            enabled: false
+        }
+    );
+
+    let result: Flag = from_values(&[Value::I32(1), Value::Str("Yes".to_string())]).unwrap();
+    assert_eq!(
+        result,
+        Flag {
+            id: 1,
+            enabled: true
+        }
+    );
+
+    let result: Flag = from_values(&[Value::I32(1), Value::Str("OFF".to_string())]).unwrap();
+    assert_eq!(
+        result,
+        Flag {
+            id: 1,
+            enabled: false
+        }
+    );
+
+    let result: Result<Flag, _> = from_values(&[Value::I32(1), Value::I32(2)]);
+    assert!(result.is_err());
+
+    let result: Result<Flag, _> = from_values(&[Value::I32(1), Value::Str("maybe".to_string())]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn full_integer_width_matrix() {
+    let vector: Vec<Value> = vec![
+        Value::I8(-1),
+        Value::U8(255),
+        Value::I128(i128::MIN),
+        Value::U128(u128::MAX),
+    ];
+    let result: Widths = from_values(&vector).unwrap();
+    assert_eq!(
+        result,
+        Widths {
+            a: -1,
+            b: 255,
+            c: i128::MIN,
+            d: u128::MAX,
+        }
+    );
+
+    // Strict mode still rejects a width mismatch for the new widths too.
+    let vector: Vec<Value> = vec![
+        Value::I16(-1),
+        Value::U8(255),
+        Value::I128(i128::MIN),
+        Value::U128(u128::MAX),
+    ];
+    let result: Result<Widths, _> = from_values(&vector);
+    assert!(result.is_err());
+
+    // Lax mode narrows as long as the value fits in the target width.
+    let vector: Vec<Value> = vec![
+        Value::I16(-1),
+        Value::U16(255),
+        Value::I64(-5),
+        Value::U64(5),
+    ];
+    let result: Widths = from_values_lax(&vector).unwrap();
+    assert_eq!(
+        result,
+        Widths {
+            a: -1,
+            b: 255,
+            c: -5,
+            d: 5,
+        }
+    );
+
+    // ...and still reports the out-of-range value/target when it doesn't.
+    let vector: Vec<Value> = vec![
+        Value::I16(1000),
+        Value::U8(255),
+        Value::I128(i128::MIN),
+        Value::U128(u128::MAX),
+    ];
+    let result: Result<Widths, _> = from_values_lax(&vector);
+    assert_eq!(
+        result,
+        Err(Error::OutOfRange {
+            value: 1000,
+            target: "i8",
+        })
+    );
+}
+
+#[test]
+fn value_into_deserializer() {
+    use serde::de::IntoDeserializer;
+
+    let result: i32 = i32::deserialize(Value::I32(42).into_deserializer()).unwrap();
+    assert_eq!(result, 42);
+
+    let value = Value::Str("hi".to_string());
+    let result: String = String::deserialize((&value).into_deserializer()).unwrap();
+    assert_eq!(result, "hi");
+
+    // Lax coercion applies to a single `Value` too, the same as `from_values_lax`.
+    let result: i64 = i64::deserialize(Value::I32(7).into_deserializer()).unwrap();
+    assert_eq!(result, 7);
+
+    let result: Result<i8, _> = i8::deserialize(Value::I32(1000).into_deserializer());
+    assert_eq!(
+        result,
+        Err(Error::OutOfRange {
+            value: 1000,
+            target: "i8",
+        })
+    );
+
+    let result: Option<i32> = Option::deserialize(Value::I32(5).into_deserializer()).unwrap();
+    assert_eq!(result, Some(5));
+
+    let result: Option<i32> = Option::deserialize(Value::Null.into_deserializer()).unwrap();
+    assert_eq!(result, None);
+}
+
 pub struct Deserializer<'de> {
     // This string starts with the input data and characters are truncated off
     // the beginning as data is parsed.
     input: &'de [Value],
     idx: usize,
+    // When set, the `next_*` integer getters coerce across integer widths
+    // instead of requiring an exact `Value` variant match. See `from_values_lax`.
+    coerce: bool,
 }
 
 impl<'de> Deserializer<'de> {
@@ -75,7 +493,21 @@ impl<'de> Deserializer<'de> {
     // `serde_json::from_str(...)` while advanced use cases that require a
     // deserializer can make one with `serde_json::Deserializer::from_str(...)`.
     pub fn from_values(input: &'de [Value]) -> Self {
-        Deserializer { input, idx: 0 }
+        Deserializer {
+            input,
+            idx: 0,
+            coerce: false,
+        }
+    }
+
+    // Like `from_values`, but integer fields are widened/narrowed across all
+    // integer `Value` variants instead of requiring an exact width match.
+    pub fn from_values_lax(input: &'de [Value]) -> Self {
+        Deserializer {
+            input,
+            idx: 0,
+            coerce: true,
+        }
     }
 }
 
@@ -96,337 +528,1304 @@ impl<'de> Deserializer<'de> {
         Ok(&self.input[old_idx])
     }
 
+    fn next_i8(&mut self) -> Result<i8, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::I8(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "i8")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "i8",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_i16(&mut self) -> Result<i16, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::I16(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "i16")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "i16",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
     fn next_i32(&mut self) -> Result<i32, Error> {
-        match *self.peek_value()? {
+        let coerce = self.coerce;
+        match self.peek_value()? {
             Value::I32(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "i32")?;
                 self.idx += 1;
                 Ok(v)
             }
-            Value::I64(_) => Err(Error::TypeMismatch {
+            other => Err(Error::TypeMismatch {
                 expected: "i32",
-                found: "i64",
+                found: value_type_name(other),
             }),
         }
     }
 
     fn next_i64(&mut self) -> Result<i64, Error> {
-        match *self.peek_value()? {
+        let coerce = self.coerce;
+        match self.peek_value()? {
             Value::I64(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "i64")?;
                 self.idx += 1;
                 Ok(v)
             }
-            Value::I32(_) => Err(Error::TypeMismatch {
+            other => Err(Error::TypeMismatch {
                 expected: "i64",
-                found: "i32",
+                found: value_type_name(other),
             }),
         }
     }
-}
 
-pub fn from_values<'a, T>(s: &'a [Value]) -> Result<T, Error>
-where
-    T: Deserialize<'a>,
-{
-    let mut deserializer = Deserializer::from_values(s);
-    let t = T::deserialize(&mut deserializer)?;
-    if deserializer.idx >= deserializer.input.len() {
-        Ok(t)
-    } else {
-        Err(Error::InputNotEmpty)
+    fn next_i128(&mut self) -> Result<i128, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::I128(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "i128")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "i128",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_u8(&mut self) -> Result<u8, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::U8(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "u8")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "u8",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_u16(&mut self) -> Result<u16, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::U16(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "u16")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "u16",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_u32(&mut self) -> Result<u32, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::U32(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "u32")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "u32",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_u64(&mut self) -> Result<u64, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::U64(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "u64")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "u64",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_u128(&mut self) -> Result<u128, Error> {
+        let coerce = self.coerce;
+        match self.peek_value()? {
+            Value::U128(v) => {
+                let v = *v;
+                self.idx += 1;
+                Ok(v)
+            }
+            other if coerce => {
+                let v = coerce_int(other, "u128")?;
+                self.idx += 1;
+                Ok(v)
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "u128",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    fn next_str(&mut self) -> Result<&str, Error> {
+        if self.idx >= self.input.len() {
+            return Err(Error::InputEmpty);
+        }
+        match &self.input[self.idx] {
+            Value::Str(s) => {
+                self.idx += 1;
+                Ok(s.as_str())
+            }
+            other => Err(Error::TypeMismatch {
+                expected: "str",
+                found: value_type_name(other),
+            }),
+        }
+    }
+
+    // Permissive by design: accepts `0`/`1` on the numeric stream, plus the
+    // usual case-insensitive truthy/falsy string tokens.
+    fn next_bool(&mut self) -> Result<bool, Error> {
+        match self.peek_value()? {
+            Value::I32(0) => {
+                self.idx += 1;
+                Ok(false)
+            }
+            Value::I32(1) => {
+                self.idx += 1;
+                Ok(true)
+            }
+            Value::Str(s) => match s.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "on" | "1" => {
+                    self.idx += 1;
+                    Ok(true)
+                }
+                "false" | "no" | "off" | "0" => {
+                    self.idx += 1;
+                    Ok(false)
+                }
+                _ => Err(Error::TypeMismatch {
+                    expected: "bool",
+                    found: "str",
+                }),
+            },
+            other => Err(Error::TypeMismatch {
+                expected: "bool",
+                found: value_type_name(other),
+            }),
+        }
+    }
+}
+
+pub fn from_values<'a, T>(s: &'a [Value]) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    finish(Deserializer::from_values(s))
+}
+
+// Like `from_values`, but converts any integer `Value` to the requested
+// integer width (e.g. `Value::I32` to `i64`, or `Value::I64` to `i32` when it
+// fits) instead of rejecting the mismatch.
+pub fn from_values_lax<'a, T>(s: &'a [Value]) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    finish(Deserializer::from_values_lax(s))
+}
+
+fn finish<'a, T>(mut deserializer: Deserializer<'a>) -> Result<T, Error>
+where
+    T: Deserialize<'a>,
+{
+    let t = T::deserialize(&mut deserializer)?;
+    if deserializer.idx >= deserializer.input.len() {
+        Ok(t)
+    } else {
+        Err(Error::InputNotEmpty)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    InputNotEmpty,
+    InputEmpty,
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+    },
+    OutOfRange {
+        value: i128,
+        target: &'static str,
+    },
+    Message(String),
+}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(std::error::Error::description(self))
+    }
+}
+
+impl std::error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::InputNotEmpty => "unexpected input remaining",
+            Error::Message(ref msg) => msg,
+            Error::InputEmpty => "unexpected end of input",
+            Error::TypeMismatch { .. } => {
+                "type mismatch detected"
+                //&format!("type error: expected `{}` but found `{}`", expected, found)
+            }
+            Error::OutOfRange { .. } => "value out of range for target type",
+        }
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.peek_value()? {
+            Value::I8(_) => self.deserialize_i8(visitor),
+            Value::I16(_) => self.deserialize_i16(visitor),
+            Value::I32(_) => self.deserialize_i32(visitor),
+            Value::I64(_) => self.deserialize_i64(visitor),
+            Value::I128(_) => self.deserialize_i128(visitor),
+            Value::U8(_) => self.deserialize_u8(visitor),
+            Value::U16(_) => self.deserialize_u16(visitor),
+            Value::U32(_) => self.deserialize_u32(visitor),
+            Value::U64(_) => self.deserialize_u64(visitor),
+            Value::U128(_) => self.deserialize_u128(visitor),
+            Value::Str(_) => self.deserialize_str(visitor),
+            Value::Null => self.deserialize_option(visitor),
+        }
+    }
+
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i32(self.next_i32()?)
+    }
+
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i64(self.next_i64()?)
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bool(self.next_bool()?)
+    }
+
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i8(self.next_i8()?)
+    }
+
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i16(self.next_i16()?)
+    }
+
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_i128(self.next_i128()?)
+    }
+
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u8(self.next_u8()?)
+    }
+
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u16(self.next_u16()?)
+    }
+
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u32(self.next_u32()?)
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u64(self.next_u64()?)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u128(self.next_u128()?)
+    }
+
+    fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.next_str()?)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_string(self.next_str()?.to_owned())
+    }
+
+    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.peek_value()? {
+            Value::Null => {
+                self.idx += 1;
+                visitor.visit_none()
+            }
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_unit<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_unit_struct<V>(
+        self,
+        _name: &'static str,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_seq(Values::new(self))
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        todo!()
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_map(Fields::new(self))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // A struct can be encoded either positionally (one value per field, in
+        // declaration order) or as an interleaved `[Str(key), value, ...]`
+        // stream. Whichever the input uses, the field name matching in the
+        // latter case is driven entirely by `deserialize_identifier` below.
+        //
+        // The two encodings are told apart by peeking whether the leading
+        // value is a `Value::Str` that actually names one of this struct's
+        // fields, rather than just any `Str`: a positionally-encoded struct
+        // whose first field is itself a `String` would otherwise be
+        // misread as map-encoded whenever its value happened to look like a
+        // key. Requiring a known field name narrows that collision down to
+        // a positional `String` field whose value happens to equal one of
+        // the struct's own field names.
+        match self.peek_value() {
+            Ok(Value::Str(s)) if fields.contains(&s.as_str()) => self.deserialize_map(visitor),
+            _ => visitor.visit_seq(PositionalFields::new(self)),
+        }
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(Enum::new(self))
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.next_str()?)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        // An unknown key in the map encoding still has a value sitting right
+        // after it; consume it so the stream stays in sync, then discard it.
+        self.peek_value()?;
+        self.idx += 1;
+        visitor.visit_unit()
+    }
+}
+
+struct Values<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'a, 'de> Values<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        Values { de }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for Values<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.de.peek_value().is_err() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct Fields<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'a, 'de> Fields<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        Fields { de }
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for Fields<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if self.de.peek_value().is_err() {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+// Like `Values`, but used for a struct's positional fields specifically: a
+// field that runs off the end of the input deserializes through
+// `MissingField` instead of ending the sequence, so a trailing `Option<T>`
+// field is filled in with `None` while any other missing field still errors.
+struct PositionalFields<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'a, 'de> PositionalFields<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        PositionalFields { de }
+    }
+}
+
+impl<'de, 'a> SeqAccess<'de> for PositionalFields<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        if self.de.peek_value().is_err() {
+            return seed.deserialize(MissingField).map(Some);
+        }
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+// Mirrors serde's own `missing_field` helper: it has no value to offer, so an
+// `Option<T>` field deserializes to `None` but anything else is an error.
+struct MissingField;
+
+impl<'de> de::Deserializer<'de> for MissingField {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        Err(Error::InputEmpty)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple tuple_struct
+        map struct enum identifier ignored_any
+    }
+}
+
+struct Enum<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'a, 'de> Enum<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        Enum { de }
+    }
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for Enum<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let idx = self.de.next_i32()?;
+        let idx = u32::try_from(idx)
+            .map_err(|_| de::Error::custom(format!("variant index out of range: {}", idx)))?;
+        let value = seed.deserialize(VariantIndexDeserializer { idx })?;
+        Ok((value, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for Enum<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_struct(self.de, "", fields, visitor)
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum Error {
-    InputNotEmpty,
-    InputEmpty,
-    TypeMismatch {
-        expected: &'static str,
-        found: &'static str,
-    },
-    Message(String),
+// A tiny helper deserializer for a single leading variant-index value, as
+// serde's own `de::value` module does for primitives: whichever
+// `deserialize_*` method is called, it hands the one value it holds to
+// `visit_u32`.
+struct VariantIndexDeserializer {
+    idx: u32,
 }
 
-impl de::Error for Error {
-    fn custom<T: Display>(msg: T) -> Self {
-        Error::Message(msg.to_string())
+impl<'de> de::Deserializer<'de> for VariantIndexDeserializer {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_u32(self.idx)
     }
-}
 
-impl Display for Error {
-    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str(std::error::Error::description(self))
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
     }
 }
 
-impl std::error::Error for Error {
-    fn description(&self) -> &str {
-        match *self {
-            Error::InputNotEmpty => "unexpected input remaining",
-            Error::Message(ref msg) => msg,
-            Error::InputEmpty => "unexpected end of input",
-            Error::TypeMismatch { .. } => {
-                "type mismatch detected"
-                //&format!("type error: expected `{}` but found `{}`", expected, found)
-            }
-        }
+// A deserializer that owns a single `Value`, the same pattern serde's own
+// `de::value` module uses for primitives: it lets one `Value` drive serde's
+// generic building blocks (e.g. a map key or a `DeserializeSeed`) without
+// constructing a whole `Vec<Value>`-backed `Deserializer`.
+pub struct ValueDeserializer {
+    value: Value,
+}
+
+impl ValueDeserializer {
+    pub fn new(value: Value) -> Self {
+        ValueDeserializer { value }
     }
 }
 
-impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
+impl<'de> de::Deserializer<'de> for ValueDeserializer {
     type Error = Error;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        match self.peek_value()? {
-            Value::I32(_) => self.deserialize_i32(visitor),
-            Value::I64(_) => self.deserialize_i64(visitor),
+        match self.value {
+            Value::I8(v) => visitor.visit_i8(v),
+            Value::I16(v) => visitor.visit_i16(v),
+            Value::I32(v) => visitor.visit_i32(v),
+            Value::I64(v) => visitor.visit_i64(v),
+            Value::I128(v) => visitor.visit_i128(v),
+            Value::U8(v) => visitor.visit_u8(v),
+            Value::U16(v) => visitor.visit_u16(v),
+            Value::U32(v) => visitor.visit_u32(v),
+            Value::U64(v) => visitor.visit_u64(v),
+            Value::U128(v) => visitor.visit_u128(v),
+            Value::Str(s) => visitor.visit_string(s),
+            Value::Null => visitor.visit_none(),
         }
     }
 
-    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_i32(self.next_i32()?)
+        match self.value {
+            Value::I8(v) => visitor.visit_i8(v),
+            other => visitor.visit_i8(coerce_int(&other, "i8")?),
+        }
     }
 
-    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        visitor.visit_i64(self.next_i64()?)
+        match self.value {
+            Value::I16(v) => visitor.visit_i16(v),
+            other => visitor.visit_i16(coerce_int(&other, "i16")?),
+        }
     }
 
-    fn deserialize_bool<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::I32(v) => visitor.visit_i32(v),
+            other => visitor.visit_i32(coerce_int(&other, "i32")?),
+        }
     }
 
-    fn deserialize_i8<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::I64(v) => visitor.visit_i64(v),
+            other => visitor.visit_i64(coerce_int(&other, "i64")?),
+        }
     }
 
-    fn deserialize_i16<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::I128(v) => visitor.visit_i128(v),
+            other => visitor.visit_i128(coerce_int(&other, "i128")?),
+        }
     }
 
-    fn deserialize_u8<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u8<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::U8(v) => visitor.visit_u8(v),
+            other => visitor.visit_u8(coerce_int(&other, "u8")?),
+        }
     }
 
-    fn deserialize_u16<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u16<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::U16(v) => visitor.visit_u16(v),
+            other => visitor.visit_u16(coerce_int(&other, "u16")?),
+        }
     }
 
-    fn deserialize_u32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u32<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::U32(v) => visitor.visit_u32(v),
+            other => visitor.visit_u32(coerce_int(&other, "u32")?),
+        }
     }
 
-    fn deserialize_u64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::U64(v) => visitor.visit_u64(v),
+            other => visitor.visit_u64(coerce_int(&other, "u64")?),
+        }
     }
 
-    fn deserialize_f32<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
-        todo!()
+        match self.value {
+            Value::U128(v) => visitor.visit_u128(v),
+            other => visitor.visit_u128(coerce_int(&other, "u128")?),
+        }
     }
 
-    fn deserialize_f64<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>,
     {
+        if matches!(self.value, Value::Null) {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool f32 f64 char str string bytes byte_buf unit unit_struct
+        newtype_struct seq tuple tuple_struct map struct enum identifier
+        ignored_any
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, Error> for Value {
+    type Deserializer = ValueDeserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer::new(self)
+    }
+}
+
+impl<'de> de::IntoDeserializer<'de, Error> for &Value {
+    type Deserializer = ValueDeserializer;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        ValueDeserializer::new(self.clone())
+    }
+}
+
+pub struct Serializer {
+    // Like `Deserializer`, this format has no length-prefixes or delimiters:
+    // compound types just append their fields to this buffer in order.
+    output: Vec<Value>,
+}
+
+// By the same `from_xyz`/`to_xyz` convention as the deserializer side.
+pub fn to_values<T>(value: &T) -> Result<Vec<Value>, Error>
+where
+    T: Serialize,
+{
+    let mut serializer = Serializer { output: Vec::new() };
+    value.serialize(&mut serializer)?;
+    Ok(serializer.output)
+}
+
+impl ser::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error::Message(msg.to_string())
+    }
+}
+
+impl ser::Serializer for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::I32(v as i32));
+        Ok(())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::I8(v));
+        Ok(())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::I16(v));
+        Ok(())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::I32(v));
+        Ok(())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::I64(v));
+        Ok(())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::U8(v));
+        Ok(())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::U16(v));
+        Ok(())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::U32(v));
+        Ok(())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::U64(v));
+        Ok(())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::I128(v));
+        Ok(())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::U128(v));
+        Ok(())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_char<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
+    fn serialize_f64(self, _v: f64) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_str<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
+    fn serialize_char(self, _v: char) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_string<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
+    fn serialize_str(self, v: &str) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::Str(v.to_owned()));
+        Ok(())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+        self.output.push(Value::Null);
+        Ok(())
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Self::Ok, Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_option<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<Self::Ok, Self::Error> {
         todo!()
     }
 
-    fn deserialize_unit<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
-        todo!()
+        value.serialize(self)
     }
 
-    fn deserialize_unit_struct<V>(
+    fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<Self::Ok, Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
         todo!()
     }
 
-    fn deserialize_newtype_struct<V>(
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
         self,
         _name: &'static str,
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, Self::Error> {
         todo!()
     }
 
-    fn deserialize_seq<V>(mut self, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        visitor.visit_seq(Values::new(&mut self))
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+        todo!()
     }
 
-    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error>
-    where
-        V: Visitor<'de>,
-    {
-        self.deserialize_seq(visitor)
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, Self::Error> {
+        Ok(self)
     }
 
-    fn deserialize_tuple_struct<V>(
+    fn serialize_struct_variant(
         self,
         _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
         _len: usize,
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
+    ) -> Result<Self::SerializeStructVariant, Self::Error> {
+        todo!()
+    }
+}
+
+impl SerializeSeq for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
-        todo!()
+        value.serialize(&mut **self)
     }
 
-    fn deserialize_map<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTuple for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
-        todo!()
+        value.serialize(&mut **self)
     }
 
-    fn deserialize_struct<V>(
-        self,
-        _name: &'static str,
-        _fields: &'static [&'static str],
-        visitor: V,
-    ) -> Result<V::Value, Self::Error>
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleStruct for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<(), Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
-        self.deserialize_seq(visitor)
+        value.serialize(&mut **self)
     }
 
-    fn deserialize_enum<V>(
-        self,
-        _name: &'static str,
-        _variants: &'static [&'static str],
-        _visitor: V,
-    ) -> Result<V::Value, Self::Error>
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
+    }
+}
+
+impl SerializeTupleVariant for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
         todo!()
     }
 
-    fn deserialize_identifier<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        todo!()
+    }
+}
+
+impl SerializeMap for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, _key: &T) -> Result<(), Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
         todo!()
     }
 
-    fn deserialize_ignored_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn serialize_value<T>(&mut self, _value: &T) -> Result<(), Self::Error>
     where
-        V: Visitor<'de>,
+        T: ?Sized + Serialize,
     {
         todo!()
     }
-}
 
-struct Values<'a, 'de: 'a> {
-    de: &'a mut Deserializer<'de>,
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        todo!()
+    }
 }
 
-impl<'a, 'de> Values<'a, 'de> {
-    fn new(de: &'a mut Deserializer<'de>) -> Self {
-        Values { de }
+impl SerializeStruct for &mut Serializer {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, _key: &'static str, value: &T) -> Result<(), Self::Error>
+    where
+        T: ?Sized + Serialize,
+    {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        Ok(())
     }
 }
 
-impl<'de, 'a> SeqAccess<'de> for Values<'a, 'de> {
+impl SerializeStructVariant for &mut Serializer {
+    type Ok = ();
     type Error = Error;
 
-    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, Self::Error>
+    fn serialize_field<T>(&mut self, _key: &'static str, _value: &T) -> Result<(), Self::Error>
     where
-        T: DeserializeSeed<'de>,
+        T: ?Sized + Serialize,
     {
-        if self.de.peek_value().is_err() {
-            return Ok(None);
-        }
-        seed.deserialize(&mut *self.de).map(Some)
+        todo!()
+    }
+
+    fn end(self) -> Result<Self::Ok, Self::Error> {
+        todo!()
     }
 }
 